@@ -0,0 +1,5 @@
+mod job;
+mod sleep;
+
+pub use job::{JobJoin, JobList};
+pub use sleep::Sleep;
@@ -1,18 +1,28 @@
+use chrono::{DateTime, FixedOffset, Local};
 use indicatif::ProgressBar;
-use nu_engine::CallExt;
+use nu_engine::{eval_block, CallExt};
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::debugger::WithoutDebug;
+use nu_protocol::engine::{Closure, Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape,
     Type, Value,
 };
+use rand::Rng;
 use std::{
+    sync::atomic::Ordering,
     thread,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
+use super::job;
+
 const CTRL_C_CHECK_INTERVAL: Duration = Duration::from_millis(100);
 
+// A gap between ticks much larger than the check interval means the process (or the whole
+// machine) was suspended for a while, rather than just scheduled a little late.
+const SUSPEND_JUMP_THRESHOLD: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 pub struct Sleep;
 
@@ -25,11 +35,63 @@ impl Command for Sleep {
         "Delay for a specified amount of time."
     }
 
+    fn extra_usage(&self) -> &str {
+        "By default, the remaining time is tracked with a monotonic clock (`Instant`), which \
+         is steady but can pause along with the process during a system suspend, causing the \
+         sleep to overshoot once the system wakes. Pass `--wall` to track the remaining time \
+         against the wall clock instead, so a suspend/resume cycle is accounted for.\n\n\
+         Pass `--while` with a closure to keep sleeping only as long as it evaluates to true, \
+         checked once per tick, instead of sleeping a fixed amount; `duration` then acts as a \
+         timeout rather than the wait time.\n\n\
+         `--jitter` and `--backoff` support retry loops: `--jitter` adds a random extra delay so \
+         concurrent retries don't stay lockstepped, and `--backoff` grows `duration` \
+         exponentially across calls using the `SLEEP_ATTEMPT` environment variable as the retry \
+         count, capped at `duration` plus any additional time arguments — which must add up to \
+         more than `duration` itself, or there's no room to grow into; once the cap is reached, \
+         `SLEEP_ATTEMPT` resets to 0 rather than growing forever."
+    }
+
     fn signature(&self) -> Signature {
         Signature::build("sleep")
             .input_output_types(vec![(Type::Nothing, Type::Nothing)])
-            .required("duration", SyntaxShape::Duration, "Time to sleep.")
+            .optional("duration", SyntaxShape::Duration, "Time to sleep.")
+            .named(
+                "until",
+                SyntaxShape::DateTime,
+                "Sleep until this wall-clock date/time is reached, instead of a relative duration.",
+                None,
+            )
             .switch("progress", "show progress/countdown bar", Some('p'))
+            .switch(
+                "wall",
+                "track remaining time against the wall clock so suspend/resume doesn't cause overshoot",
+                None,
+            )
+            .switch(
+                "background",
+                "run the sleep on a background job and return a handle instead of blocking; join it with `job join`",
+                Some('b'),
+            )
+            .named(
+                "while",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Duration])),
+                "Re-check this closure, called with the elapsed time, every tick and keep \
+                 sleeping as long as it's true, returning as soon as it's false. `duration` \
+                 becomes a timeout.",
+                None,
+            )
+            .named(
+                "jitter",
+                SyntaxShape::Duration,
+                "Add a random extra delay between 0 and this duration, so repeated sleeps don't stay in lockstep.",
+                None,
+            )
+            .named(
+                "backoff",
+                SyntaxShape::Number,
+                "Exponentially widen `duration` by this factor per retry (tracked in $env.SLEEP_ATTEMPT), capped at `duration` plus any additional time arguments, which must exceed `duration`.",
+                None,
+            )
             .rest("rest", SyntaxShape::Duration, "Additional time.")
             .category(Category::Platform)
     }
@@ -49,51 +111,163 @@ impl Command for Sleep {
             Duration::from_nanos(if val < 0 { 0 } else { val as u64 })
         }
 
-        let duration: i64 = call.req(engine_state, stack, 0)?;
-        let rest: Vec<i64> = call.rest(engine_state, stack, 1)?;
+        let until: Option<Value> = call.get_flag(engine_state, stack, "until")?;
+
+        let (total_dur, until_label) = if let Some(until) = until {
+            let target = until.as_date()?;
+            let now: DateTime<FixedOffset> = Local::now().into();
+            let remaining = target.signed_duration_since(now);
+            let remaining = remaining.to_std().map_err(|_| ShellError::IncorrectValue {
+                msg: "`--until` time is already in the past".into(),
+                val_span: until.span(),
+                call_span: call.head,
+            })?;
+            (remaining, Some(target.format("%Y-%m-%dT%H:%M:%S%:z").to_string()))
+        } else {
+            let duration: i64 = call.req(engine_state, stack, 0)?;
+            let rest: Vec<i64> = call.rest(engine_state, stack, 1)?;
 
-        let total_dur =
-            duration_from_i64(duration) + rest.into_iter().map(duration_from_i64).sum::<Duration>();
+            let base_dur = duration_from_i64(duration);
+            let cap_dur =
+                base_dur + rest.into_iter().map(duration_from_i64).sum::<Duration>();
 
-        let ctrlc_ref = &engine_state.ctrlc.clone();
-        let start = Instant::now();
-        let should_progress = if matches!(call.has_flag(engine_state, stack, "progress"), Ok(true))
+            let total_dur = if let Some(factor) = call
+                .get_flag::<f64>(engine_state, stack, "backoff")?
+            {
+                // With no additional time arguments, `cap_dur == base_dur` and there's no room to
+                // grow into — `sleep 1sec --backoff 2` would silently do nothing. Require a cap
+                // bigger than `duration` instead of letting that pass quietly.
+                if cap_dur <= base_dur {
+                    return Err(ShellError::IncorrectValue {
+                        msg: "`--backoff` needs a cap larger than `duration` to have room to \
+                              grow into; pass additional time arguments, e.g. \
+                              `sleep 1sec 30sec --backoff 2`"
+                            .into(),
+                        val_span: call.head,
+                        call_span: call.head,
+                    });
+                }
+
+                // `SLEEP_ATTEMPT` lets a `loop { ... ; sleep 1sec --backoff 2 }` retry widen its
+                // own wait each time around without the caller threading a counter through by
+                // hand; the env var is how state crosses invocations in a script loop.
+                let attempt = stack
+                    .get_env_var(engine_state, "SLEEP_ATTEMPT")
+                    .and_then(|v| v.as_int().ok())
+                    .unwrap_or(0)
+                    .clamp(0, i64::from(i32::MAX));
+
+                let cap_secs = cap_dur.as_secs_f64();
+                // Clamp in `f64` space *before* building the `Duration`: `factor.powi(attempt)`
+                // can grow far past what `Duration::from_secs_f64` accepts (it panics on a value
+                // too large for a `Duration`, and on `f64::INFINITY`, which a large attempt
+                // count reaches).
+                let scaled_secs = (base_dur.as_secs_f64() * factor.max(1.0).powi(attempt as i32))
+                    .min(cap_secs)
+                    .max(base_dur.as_secs_f64());
+
+                // Once the curve has maxed out, start it over on the next call instead of
+                // leaving `SLEEP_ATTEMPT` climbing forever across a long retry loop.
+                let next_attempt = if scaled_secs >= cap_secs { 0 } else { attempt + 1 };
+                stack.add_env_var("SLEEP_ATTEMPT".into(), Value::int(next_attempt, call.head));
+
+                Duration::from_secs_f64(scaled_secs)
+            } else {
+                cap_dur
+            };
+
+            (total_dur, None)
+        };
+
+        let total_dur = if let Some(jitter) = call.get_flag::<i64>(engine_state, stack, "jitter")?
         {
-            let tsecs = total_dur.as_secs();
-            let thour = tsecs / 3600;
-            let tmin = (tsecs % 3600) / 60;
-            let tsec = tsecs % 60;
-
-            let timeout_str = format!("{:02}:{:02}:{:02}", thour, tmin, tsec);
-            Some(
-                indicatif::ProgressBar::new((total_dur.as_millis() / 10) as u64)
-                    .with_message(timeout_str)
-                    .with_style(
-                        indicatif::ProgressStyle::with_template(
-                            "{wide_bar}[{elapsed_precise} / {msg}]",
-                        )
-                        .unwrap(),
-                    ),
-            )
+            let jitter_nanos = duration_from_i64(jitter).as_nanos() as u64;
+            let extra = rand::thread_rng().gen_range(0..=jitter_nanos);
+            total_dur + Duration::from_nanos(extra)
         } else {
-            None
+            total_dur
         };
 
-        loop {
-            thread::sleep(CTRL_C_CHECK_INTERVAL);
-            let time_elapsed = start.elapsed();
-            if time_elapsed >= total_dur {
-                break;
-            }
-            if let Some(ref pb) = should_progress {
-                pb.set_position((time_elapsed.as_millis() / 10) as u64);
-            }
+        let use_wall_clock = call.has_flag(engine_state, stack, "wall")?;
+        let should_progress = matches!(call.has_flag(engine_state, stack, "progress"), Ok(true));
+        let progress_label = until_label.unwrap_or_else(|| {
+            let tsecs = total_dur.as_secs();
+            format!(
+                "{:02}:{:02}:{:02}",
+                tsecs / 3600,
+                (tsecs % 3600) / 60,
+                tsecs % 60
+            )
+        });
+
+        let head = call.head;
+        let while_closure: Option<Closure> = call.get_flag(engine_state, stack, "while")?;
 
-            if nu_utils::ctrl_c::was_pressed(ctrlc_ref) {
-                return Err(ShellError::InterruptedByUser {
-                    span: Some(call.head),
+        if call.has_flag(engine_state, stack, "background")? {
+            if while_closure.is_some() {
+                return Err(ShellError::IncorrectValue {
+                    msg: "`--while` cannot be combined with `--background` yet".into(),
+                    val_span: head,
+                    call_span: head,
                 });
             }
+
+            let id = job::spawn_job(total_dur, move |cancel| {
+                run_sleep_loop(
+                    total_dur,
+                    use_wall_clock,
+                    should_progress,
+                    &progress_label,
+                    Some(head),
+                    || cancel.load(Ordering::SeqCst),
+                    |_elapsed| Ok(true),
+                )
+            });
+            return Ok(Value::int(id as i64, call.head).into_pipeline_data());
+        }
+
+        let ctrlc_ref = engine_state.ctrlc.clone();
+        match while_closure {
+            Some(closure) => {
+                let block = engine_state.get_block(closure.block_id);
+                let mut closure_stack = stack.captures_to_stack(closure.captures.clone());
+                run_sleep_loop(
+                    total_dur,
+                    use_wall_clock,
+                    should_progress,
+                    &progress_label,
+                    Some(head),
+                    || nu_utils::ctrl_c::was_pressed(&ctrlc_ref),
+                    |elapsed| {
+                        let elapsed_val = Value::duration(elapsed.as_nanos() as i64, head);
+                        // The closure is documented as "called with the elapsed time", i.e. bound
+                        // to its first positional (`{|elapsed| ...}`), not just piped in as `$in`.
+                        if let Some(var_id) = block
+                            .signature
+                            .required_positional
+                            .first()
+                            .and_then(|p| p.var_id)
+                        {
+                            closure_stack.add_var(var_id, elapsed_val.clone());
+                        }
+                        let input = elapsed_val.into_pipeline_data();
+                        let out =
+                            eval_block::<WithoutDebug>(engine_state, &mut closure_stack, block, input)?;
+                        out.into_value(head)?.as_bool()
+                    },
+                )?;
+            }
+            None => {
+                run_sleep_loop(
+                    total_dur,
+                    use_wall_clock,
+                    should_progress,
+                    &progress_label,
+                    Some(head),
+                    || nu_utils::ctrl_c::was_pressed(&ctrlc_ref),
+                    |_elapsed| Ok(true),
+                )?;
+            }
         }
 
         Ok(Value::nothing(call.head).into_pipeline_data())
@@ -116,10 +290,110 @@ impl Command for Sleep {
                 example: "sleep 1sec; echo done",
                 result: None,
             },
+            Example {
+                description: "Sleep until a fixed point in wall-clock time",
+                example: "sleep --until 2025-01-01T00:00:00",
+                result: None,
+            },
+            Example {
+                description: "Sleep in the background and join it later",
+                example: "let handle = sleep 5sec --background; job join $handle",
+                result: None,
+            },
+            Example {
+                description: "Wait for a file to appear, with a 30sec timeout",
+                example: "sleep 30sec --while {|elapsed| not (\"ready\" | path exists) }",
+                result: None,
+            },
+            Example {
+                description: "Stop as soon as the elapsed time bound to the closure's own parameter passes 50ms",
+                example: "sleep 5sec --while {|elapsed| $elapsed < 50ms}",
+                result: Some(Value::nothing(Span::test_data())),
+            },
+            Example {
+                description: "Sleep 1sec plus up to 500ms of jitter",
+                example: "sleep 1sec --jitter 500ms",
+                result: None,
+            },
+            Example {
+                description: "Retry loop with exponential backoff capped at 30sec",
+                example: "loop { sleep 1sec 30sec --backoff 2; try { http get https://example.com; break } }",
+                result: None,
+            },
         ]
     }
 }
 
+/// Shared by the foreground and background (`--background`) code paths: ticks every
+/// `CTRL_C_CHECK_INTERVAL`, updates an optional progress bar, and returns as soon as either
+/// `is_cancelled` reports true, `keep_sleeping` (the `--while` closure, evaluated with the
+/// elapsed time) reports false, or `total_dur` elapses.
+fn run_sleep_loop(
+    total_dur: Duration,
+    use_wall_clock: bool,
+    show_progress: bool,
+    progress_label: &str,
+    cancel_span: Option<Span>,
+    mut is_cancelled: impl FnMut() -> bool,
+    mut keep_sleeping: impl FnMut(Duration) -> Result<bool, ShellError>,
+) -> Result<(), ShellError> {
+    let start = Instant::now();
+    let wall_start = SystemTime::now();
+    let should_progress = show_progress.then(|| {
+        indicatif::ProgressBar::new((total_dur.as_millis() / 10) as u64)
+            .with_message(progress_label.to_string())
+            .with_style(
+                indicatif::ProgressStyle::with_template("{wide_bar}[{elapsed_precise} / {msg}]")
+                    .unwrap(),
+            )
+    });
+
+    let mut last_wall_tick = SystemTime::now();
+    loop {
+        thread::sleep(CTRL_C_CHECK_INTERVAL);
+
+        let time_elapsed = if use_wall_clock {
+            // `time_elapsed` itself is already resynced every tick below, since it's recomputed
+            // from `wall_start` rather than accumulated — a suspend doesn't throw it off. The one
+            // thing that *doesn't* self-correct is indicatif's progress bar, whose
+            // `{elapsed_precise}` is backed by its own `Instant` and, like the default mode's
+            // clock, can pause during a suspend. The tick-to-tick gap is therefore measured on
+            // the wall clock, where a suspend shows up; on a large gap the bar's elapsed counter
+            // is restarted. This branch is progress-bar bookkeeping, not part of the
+            // remaining-time math.
+            let now = SystemTime::now();
+            let woke_from_suspend = now
+                .duration_since(last_wall_tick)
+                .map_or(false, |gap| gap > SUSPEND_JUMP_THRESHOLD);
+            last_wall_tick = now;
+            if woke_from_suspend {
+                if let Some(ref pb) = should_progress {
+                    pb.reset_elapsed();
+                }
+            }
+            wall_start.elapsed().unwrap_or(Duration::ZERO)
+        } else {
+            // No suspend/resume handling here by design: the default clock is monotonic and can
+            // overshoot across a suspend, which is exactly why `--wall` exists (see
+            // `extra_usage`). Pass `--wall` for a sleep that needs to survive one.
+            start.elapsed()
+        };
+
+        if !keep_sleeping(time_elapsed)? || time_elapsed >= total_dur {
+            break;
+        }
+        if let Some(ref pb) = should_progress {
+            pb.set_position((time_elapsed.as_millis() / 10) as u64);
+        }
+
+        if is_cancelled() {
+            return Err(ShellError::InterruptedByUser { span: cancel_span });
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::Sleep;